@@ -1,20 +1,415 @@
 //! `Attribute`s are optional modifiers to functions, function parameters, and return types.
 
 use llvm_sys::core::{
-    LLVMGetEnumAttributeKind, LLVMGetEnumAttributeKindForName, LLVMGetEnumAttributeValue, LLVMGetLastEnumAttributeKind,
-    LLVMGetStringAttributeKind, LLVMGetStringAttributeValue, LLVMIsEnumAttribute, LLVMIsStringAttribute,
+    LLVMGetAttributeCountAtIndex, LLVMGetAttributesAtIndex, LLVMGetEnumAttributeKind, LLVMGetEnumAttributeKindForName,
+    LLVMGetEnumAttributeValue, LLVMGetLastEnumAttributeKind, LLVMGetStringAttributeKind, LLVMGetStringAttributeValue,
+    LLVMIsEnumAttribute, LLVMIsStringAttribute,
 };
 #[llvm_versions(12.0..=latest)]
 use llvm_sys::core::{LLVMGetTypeAttributeValue, LLVMIsTypeAttribute};
-use llvm_sys::prelude::LLVMAttributeRef;
+use llvm_sys::prelude::{LLVMAttributeRef, LLVMValueRef};
 
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::sync::OnceLock;
 
 #[llvm_versions(12.0..=latest)]
 use crate::types::AnyTypeEnum;
+use crate::context::Context;
 #[cfg(feature = "internal-getters")]
 use crate::LLVMReference;
 
+/// A typed enumeration of LLVM's builtin enum `Attribute`s.
+///
+/// This exists so that callers don't have to round-trip builtin attribute
+/// names through [`Attribute::get_named_enum_kind_id`] as raw strings, where
+/// a typo silently resolves to kind id `0` instead of failing to compile.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::attributes::AttributeKind;
+///
+/// let kind_id = AttributeKind::AlwaysInline.kind_id();
+///
+/// assert_eq!(AttributeKind::from_name("alwaysinline"), Some(AttributeKind::AlwaysInline));
+/// assert_eq!(AttributeKind::from_name("not_a_real_attribute"), None);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum AttributeKind {
+    /// `align`
+    Align,
+    /// `alignstack`
+    AlignStack,
+    /// `allocsize`
+    AllocSize,
+    /// `alwaysinline`
+    AlwaysInline,
+    /// `argmemonly`
+    ArgMemOnly,
+    /// `builtin`
+    Builtin,
+    /// `byval`
+    ByVal,
+    /// `cold`
+    Cold,
+    /// `convergent`
+    Convergent,
+    /// `dereferenceable`
+    Dereferenceable,
+    /// `dereferenceable_or_null`
+    DereferenceableOrNull,
+    /// `inalloca`
+    InAlloca,
+    /// `inlinehint`
+    InlineHint,
+    /// `inreg`
+    InReg,
+    /// `jumptable`
+    JumpTable,
+    /// `memory`
+    Memory,
+    /// `minsize`
+    MinSize,
+    /// `naked`
+    Naked,
+    /// `nest`
+    Nest,
+    /// `noalias`
+    NoAlias,
+    /// `nobuiltin`
+    NoBuiltin,
+    /// `nocapture`
+    NoCapture,
+    /// `noduplicate`
+    NoDuplicate,
+    /// `nofree`
+    NoFree,
+    /// `noimplicitfloat`
+    NoImplicitFloat,
+    /// `noinline`
+    NoInline,
+    /// `nonlazybind`
+    NonLazyBind,
+    /// `noredzone`
+    NoRedZone,
+    /// `noreturn`
+    NoReturn,
+    /// `nosync`
+    NoSync,
+    /// `nounwind`
+    NoUnwind,
+    /// `optnone`
+    OptNone,
+    /// `optsize`
+    OptSize,
+    /// `readnone`
+    ReadNone,
+    /// `readonly`
+    ReadOnly,
+    /// `returned`
+    Returned,
+    /// `returns_twice`
+    ReturnsTwice,
+    /// `signext`
+    SExt,
+    /// `safestack`
+    SafeStack,
+    /// `sanitize_address`
+    SanitizeAddress,
+    /// `sanitize_hwaddress`
+    SanitizeHwAddress,
+    /// `sanitize_memory`
+    SanitizeMemory,
+    /// `sanitize_thread`
+    SanitizeThread,
+    /// `shadowcallstack`
+    ShadowCallStack,
+    /// `speculatable`
+    Speculatable,
+    /// `sspreq`
+    StackProtectReq,
+    /// `sspstrong`
+    StackProtectStrong,
+    /// `ssp`
+    StackProtect,
+    /// `strictfp`
+    StrictFp,
+    /// `sret`
+    StructRet,
+    /// `swifterror`
+    SwiftError,
+    /// `swiftself`
+    SwiftSelf,
+    /// `uwtable`
+    UwTable,
+    /// `willreturn`
+    WillReturn,
+    /// `writeonly`
+    WriteOnly,
+    /// `zeroext`
+    ZExt,
+}
+
+impl AttributeKind {
+    /// Gets the canonical LLVM name of this `AttributeKind`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::AttributeKind;
+    ///
+    /// assert_eq!(AttributeKind::ByVal.name(), "byval");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            AttributeKind::Align => "align",
+            AttributeKind::AlignStack => "alignstack",
+            AttributeKind::AllocSize => "allocsize",
+            AttributeKind::AlwaysInline => "alwaysinline",
+            AttributeKind::ArgMemOnly => "argmemonly",
+            AttributeKind::Builtin => "builtin",
+            AttributeKind::ByVal => "byval",
+            AttributeKind::Cold => "cold",
+            AttributeKind::Convergent => "convergent",
+            AttributeKind::Dereferenceable => "dereferenceable",
+            AttributeKind::DereferenceableOrNull => "dereferenceable_or_null",
+            AttributeKind::InAlloca => "inalloca",
+            AttributeKind::InlineHint => "inlinehint",
+            AttributeKind::InReg => "inreg",
+            AttributeKind::JumpTable => "jumptable",
+            AttributeKind::Memory => "memory",
+            AttributeKind::MinSize => "minsize",
+            AttributeKind::Naked => "naked",
+            AttributeKind::Nest => "nest",
+            AttributeKind::NoAlias => "noalias",
+            AttributeKind::NoBuiltin => "nobuiltin",
+            AttributeKind::NoCapture => "nocapture",
+            AttributeKind::NoDuplicate => "noduplicate",
+            AttributeKind::NoFree => "nofree",
+            AttributeKind::NoImplicitFloat => "noimplicitfloat",
+            AttributeKind::NoInline => "noinline",
+            AttributeKind::NonLazyBind => "nonlazybind",
+            AttributeKind::NoRedZone => "noredzone",
+            AttributeKind::NoReturn => "noreturn",
+            AttributeKind::NoSync => "nosync",
+            AttributeKind::NoUnwind => "nounwind",
+            AttributeKind::OptNone => "optnone",
+            AttributeKind::OptSize => "optsize",
+            AttributeKind::ReadNone => "readnone",
+            AttributeKind::ReadOnly => "readonly",
+            AttributeKind::Returned => "returned",
+            AttributeKind::ReturnsTwice => "returns_twice",
+            AttributeKind::SExt => "signext",
+            AttributeKind::SafeStack => "safestack",
+            AttributeKind::SanitizeAddress => "sanitize_address",
+            AttributeKind::SanitizeHwAddress => "sanitize_hwaddress",
+            AttributeKind::SanitizeMemory => "sanitize_memory",
+            AttributeKind::SanitizeThread => "sanitize_thread",
+            AttributeKind::ShadowCallStack => "shadowcallstack",
+            AttributeKind::Speculatable => "speculatable",
+            AttributeKind::StackProtectReq => "sspreq",
+            AttributeKind::StackProtectStrong => "sspstrong",
+            AttributeKind::StackProtect => "ssp",
+            AttributeKind::StrictFp => "strictfp",
+            AttributeKind::StructRet => "sret",
+            AttributeKind::SwiftError => "swifterror",
+            AttributeKind::SwiftSelf => "swiftself",
+            AttributeKind::UwTable => "uwtable",
+            AttributeKind::WillReturn => "willreturn",
+            AttributeKind::WriteOnly => "writeonly",
+            AttributeKind::ZExt => "zeroext",
+        }
+    }
+
+    /// Looks up an `AttributeKind` from its canonical LLVM name.
+    ///
+    /// Returns `None` if `name` isn't a recognized builtin attribute name,
+    /// rather than silently falling back to kind id `0` the way
+    /// [`Attribute::get_named_enum_kind_id`] does.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::AttributeKind;
+    ///
+    /// assert_eq!(AttributeKind::from_name("noinline"), Some(AttributeKind::NoInline));
+    /// assert_eq!(AttributeKind::from_name("foobar"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "align" => AttributeKind::Align,
+            "alignstack" => AttributeKind::AlignStack,
+            "allocsize" => AttributeKind::AllocSize,
+            "alwaysinline" => AttributeKind::AlwaysInline,
+            "argmemonly" => AttributeKind::ArgMemOnly,
+            "builtin" => AttributeKind::Builtin,
+            "byval" => AttributeKind::ByVal,
+            "cold" => AttributeKind::Cold,
+            "convergent" => AttributeKind::Convergent,
+            "dereferenceable" => AttributeKind::Dereferenceable,
+            "dereferenceable_or_null" => AttributeKind::DereferenceableOrNull,
+            "inalloca" => AttributeKind::InAlloca,
+            "inlinehint" => AttributeKind::InlineHint,
+            "inreg" => AttributeKind::InReg,
+            "jumptable" => AttributeKind::JumpTable,
+            "memory" => AttributeKind::Memory,
+            "minsize" => AttributeKind::MinSize,
+            "naked" => AttributeKind::Naked,
+            "nest" => AttributeKind::Nest,
+            "noalias" => AttributeKind::NoAlias,
+            "nobuiltin" => AttributeKind::NoBuiltin,
+            "nocapture" => AttributeKind::NoCapture,
+            "noduplicate" => AttributeKind::NoDuplicate,
+            "nofree" => AttributeKind::NoFree,
+            "noimplicitfloat" => AttributeKind::NoImplicitFloat,
+            "noinline" => AttributeKind::NoInline,
+            "nonlazybind" => AttributeKind::NonLazyBind,
+            "noredzone" => AttributeKind::NoRedZone,
+            "noreturn" => AttributeKind::NoReturn,
+            "nosync" => AttributeKind::NoSync,
+            "nounwind" => AttributeKind::NoUnwind,
+            "optnone" => AttributeKind::OptNone,
+            "optsize" => AttributeKind::OptSize,
+            "readnone" => AttributeKind::ReadNone,
+            "readonly" => AttributeKind::ReadOnly,
+            "returned" => AttributeKind::Returned,
+            "returns_twice" => AttributeKind::ReturnsTwice,
+            "signext" => AttributeKind::SExt,
+            "safestack" => AttributeKind::SafeStack,
+            "sanitize_address" => AttributeKind::SanitizeAddress,
+            "sanitize_hwaddress" => AttributeKind::SanitizeHwAddress,
+            "sanitize_memory" => AttributeKind::SanitizeMemory,
+            "sanitize_thread" => AttributeKind::SanitizeThread,
+            "shadowcallstack" => AttributeKind::ShadowCallStack,
+            "speculatable" => AttributeKind::Speculatable,
+            "sspreq" => AttributeKind::StackProtectReq,
+            "sspstrong" => AttributeKind::StackProtectStrong,
+            "ssp" => AttributeKind::StackProtect,
+            "strictfp" => AttributeKind::StrictFp,
+            "sret" => AttributeKind::StructRet,
+            "swifterror" => AttributeKind::SwiftError,
+            "swiftself" => AttributeKind::SwiftSelf,
+            "uwtable" => AttributeKind::UwTable,
+            "willreturn" => AttributeKind::WillReturn,
+            "writeonly" => AttributeKind::WriteOnly,
+            "zeroext" => AttributeKind::ZExt,
+            _ => return None,
+        })
+    }
+
+    /// Gets the kind id LLVM currently has assigned to this `AttributeKind`.
+    ///
+    /// Kind ids aren't stable across LLVM versions, so this always resolves
+    /// the name via `LLVMGetEnumAttributeKindForName` rather than caching a
+    /// fixed number.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::{Attribute, AttributeKind};
+    ///
+    /// assert_eq!(AttributeKind::ByVal.kind_id(), Attribute::get_named_enum_kind_id("byval"));
+    /// ```
+    pub fn kind_id(self) -> u32 {
+        Attribute::get_named_enum_kind_id(self.name())
+    }
+
+    /// Looks up the `AttributeKind` (if any) matching a raw kind id, as
+    /// returned by [`Attribute::get_enum_kind_id`].
+    ///
+    /// The first call resolves every builtin name to its current kind id (one
+    /// `LLVMGetEnumAttributeKindForName` call per variant) and caches the
+    /// resulting map; subsequent lookups, e.g. while diffing or copying the
+    /// attributes on a function, are a plain hash map lookup.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::AttributeKind;
+    ///
+    /// let kind_id = AttributeKind::NoInline.kind_id();
+    ///
+    /// assert_eq!(AttributeKind::from_kind_id(kind_id), Some(AttributeKind::NoInline));
+    /// ```
+    pub fn from_kind_id(kind_id: u32) -> Option<Self> {
+        static KIND_IDS_BY_ID: OnceLock<HashMap<u32, AttributeKind>> = OnceLock::new();
+
+        KIND_IDS_BY_ID
+            .get_or_init(|| {
+                // Names the running LLVM doesn't know resolve to kind id 0, which
+                // isn't a real attribute, so keep them out of the map.
+                ALL_ATTRIBUTE_KINDS
+                    .iter()
+                    .map(|&kind| (kind.kind_id(), kind))
+                    .filter(|&(kind_id, _)| kind_id != 0)
+                    .collect()
+            })
+            .get(&kind_id)
+            .copied()
+    }
+}
+
+const ALL_ATTRIBUTE_KINDS: &[AttributeKind] = &[
+    AttributeKind::Align,
+    AttributeKind::AlignStack,
+    AttributeKind::AllocSize,
+    AttributeKind::AlwaysInline,
+    AttributeKind::ArgMemOnly,
+    AttributeKind::Builtin,
+    AttributeKind::ByVal,
+    AttributeKind::Cold,
+    AttributeKind::Convergent,
+    AttributeKind::Dereferenceable,
+    AttributeKind::DereferenceableOrNull,
+    AttributeKind::InAlloca,
+    AttributeKind::InlineHint,
+    AttributeKind::InReg,
+    AttributeKind::JumpTable,
+    AttributeKind::Memory,
+    AttributeKind::MinSize,
+    AttributeKind::Naked,
+    AttributeKind::Nest,
+    AttributeKind::NoAlias,
+    AttributeKind::NoBuiltin,
+    AttributeKind::NoCapture,
+    AttributeKind::NoDuplicate,
+    AttributeKind::NoFree,
+    AttributeKind::NoImplicitFloat,
+    AttributeKind::NoInline,
+    AttributeKind::NonLazyBind,
+    AttributeKind::NoRedZone,
+    AttributeKind::NoReturn,
+    AttributeKind::NoSync,
+    AttributeKind::NoUnwind,
+    AttributeKind::OptNone,
+    AttributeKind::OptSize,
+    AttributeKind::ReadNone,
+    AttributeKind::ReadOnly,
+    AttributeKind::Returned,
+    AttributeKind::ReturnsTwice,
+    AttributeKind::SExt,
+    AttributeKind::SafeStack,
+    AttributeKind::SanitizeAddress,
+    AttributeKind::SanitizeHwAddress,
+    AttributeKind::SanitizeMemory,
+    AttributeKind::SanitizeThread,
+    AttributeKind::ShadowCallStack,
+    AttributeKind::Speculatable,
+    AttributeKind::StackProtectReq,
+    AttributeKind::StackProtectStrong,
+    AttributeKind::StackProtect,
+    AttributeKind::StrictFp,
+    AttributeKind::StructRet,
+    AttributeKind::SwiftError,
+    AttributeKind::SwiftSelf,
+    AttributeKind::UwTable,
+    AttributeKind::WillReturn,
+    AttributeKind::WriteOnly,
+    AttributeKind::ZExt,
+];
+
 // SubTypes: Attribute<Enum>, Attribute<String>
 /// Functions, function parameters, and return types can have `Attribute`s to indicate
 /// how they should be treated by optimizations and code generation.
@@ -30,9 +425,11 @@ impl Attribute {
         Attribute { attribute }
     }
 
-    /// Determines whether or not an `Attribute` is an enum. This method will
-    /// likely be removed in the future in favor of `Attribute`s being generically
-    /// defined.
+    /// Determines whether or not an `Attribute` is an enum.
+    ///
+    /// Prefer [`Attribute::try_into_enum`] when you want to go on to call
+    /// enum-only accessors, since it downcasts to an [`EnumAttribute`] in one
+    /// step instead of asserting on every accessor call.
     ///
     /// # Example
     ///
@@ -48,9 +445,11 @@ impl Attribute {
         unsafe { LLVMIsEnumAttribute(self.attribute) == 1 }
     }
 
-    /// Determines whether or not an `Attribute` is a string. This method will
-    /// likely be removed in the future in favor of `Attribute`s being generically
-    /// defined.
+    /// Determines whether or not an `Attribute` is a string.
+    ///
+    /// Prefer [`Attribute::try_into_string`] when you want to go on to call
+    /// string-only accessors, since it downcasts to a [`StringAttribute`] in
+    /// one step instead of asserting on every accessor call.
     ///
     /// # Example
     ///
@@ -66,9 +465,11 @@ impl Attribute {
         unsafe { LLVMIsStringAttribute(self.attribute) == 1 }
     }
 
-    /// Determines whether or not an `Attribute` is a type attribute. This method will
-    /// likely be removed in the future in favor of `Attribute`s being generically
-    /// defined.
+    /// Determines whether or not an `Attribute` is a type attribute.
+    ///
+    /// Prefer [`Attribute::try_into_type`] when you want to go on to call
+    /// type-only accessors, since it downcasts to a [`TypeAttribute`] in one
+    /// step instead of asserting on every accessor call.
     ///
     /// # Example
     ///
@@ -90,6 +491,69 @@ impl Attribute {
         unsafe { LLVMIsTypeAttribute(self.attribute) == 1 }
     }
 
+    /// Attempts to downcast this untyped `Attribute` handle into an
+    /// [`EnumAttribute`], returning `None` if it isn't an enum attribute.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let enum_attribute = context.create_enum_attribute(0, 10);
+    ///
+    /// assert!(enum_attribute.try_into_enum().is_some());
+    /// ```
+    pub fn try_into_enum(self) -> Option<EnumAttribute> {
+        self.is_enum().then_some(EnumAttribute {
+            attribute: self.attribute,
+        })
+    }
+
+    /// Attempts to downcast this untyped `Attribute` handle into a
+    /// [`StringAttribute`], returning `None` if it isn't a string attribute.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let string_attribute = context.create_string_attribute("my_key", "my_val");
+    ///
+    /// assert!(string_attribute.try_into_string().is_some());
+    /// ```
+    pub fn try_into_string(self) -> Option<StringAttribute> {
+        self.is_string().then_some(StringAttribute {
+            attribute: self.attribute,
+        })
+    }
+
+    /// Attempts to downcast this untyped `Attribute` handle into a
+    /// [`TypeAttribute`], returning `None` if it isn't a type attribute.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::attributes::Attribute;
+    ///
+    /// let context = Context::create();
+    /// let kind_id = Attribute::get_named_enum_kind_id("sret");
+    /// let type_attribute = context.create_type_attribute(
+    ///     kind_id,
+    ///     context.i32_type().into(),
+    /// );
+    ///
+    /// assert!(type_attribute.try_into_type().is_some());
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn try_into_type(self) -> Option<TypeAttribute> {
+        self.is_type().then_some(TypeAttribute {
+            attribute: self.attribute,
+        })
+    }
+
     /// Gets the enum kind id associated with a builtin name.
     ///
     /// # Example
@@ -142,21 +606,33 @@ impl Attribute {
     /// ```
     #[llvm_versions(12.0..=latest)]
     pub fn get_enum_kind_id(self) -> u32 {
-        assert!(self.get_enum_kind_id_is_valid()); // FIXME: SubTypes
-
-        unsafe {
-            LLVMGetEnumAttributeKind(self.attribute)
-        }
-    }
-
-    #[llvm_versions(4.0..12.0)]
-    fn get_enum_kind_id_is_valid(self) -> bool {
-        self.is_enum()
+        self.try_into_enum()
+            .map(|attribute| attribute.get_enum_kind_id())
+            .or_else(|| self.try_into_type().map(|attribute| attribute.get_enum_kind_id()))
+            .expect("Attribute is neither an enum nor a type attribute")
     }
 
+    /// Gets the `AttributeKind` associated with an enum `Attribute`, if it's
+    /// one of LLVM's recognized builtin attributes.
+    ///
+    /// Returns `None` if the kind id doesn't map to any known
+    /// [`AttributeKind`], which can happen for target-specific attributes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::AttributeKind;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let kind_id = AttributeKind::NoUnwind.kind_id();
+    /// let enum_attribute = context.create_enum_attribute(kind_id, 0);
+    ///
+    /// assert_eq!(enum_attribute.get_enum_kind(), Some(AttributeKind::NoUnwind));
+    /// ```
     #[llvm_versions(12.0..=latest)]
-    fn get_enum_kind_id_is_valid(self) -> bool {
-        self.is_enum() || self.is_type()
+    pub fn get_enum_kind(self) -> Option<AttributeKind> {
+        AttributeKind::from_kind_id(self.get_enum_kind_id())
     }
 
     /// Gets the last enum kind id associated with builtin names.
@@ -185,9 +661,9 @@ impl Attribute {
     /// assert_eq!(enum_attribute.get_enum_value(), 10);
     /// ```
     pub fn get_enum_value(self) -> u64 {
-        assert!(self.is_enum()); // FIXME: SubTypes
-
-        unsafe { LLVMGetEnumAttributeValue(self.attribute) }
+        self.try_into_enum()
+            .expect("Attribute is not an enum attribute")
+            .get_enum_value()
     }
 
     /// Gets the string kind id associated with a string attribute.
@@ -204,7 +680,11 @@ impl Attribute {
     /// ```
     // TODO: Check if null, return option
     pub fn get_string_kind_id(&self) -> &CStr {
-        assert!(self.is_string()); // FIXME: SubTypes
+        // Can't delegate to `StringAttribute::get_string_kind_id` and return its
+        // result here: that method borrows a temporary `StringAttribute`, whose
+        // lifetime can't be stretched to match `&self`. Go through `try_into_string`
+        // purely to validate, then read the same way `StringAttribute` does.
+        self.try_into_string().expect("Attribute is not a string attribute");
 
         let mut length = 0;
         let cstr_ptr = unsafe { LLVMGetStringAttributeKind(self.attribute, &mut length) };
@@ -225,7 +705,9 @@ impl Attribute {
     /// assert_eq!(string_attribute.get_string_value().to_str(), Ok("my_val"));
     /// ```
     pub fn get_string_value(&self) -> &CStr {
-        assert!(self.is_string()); // FIXME: SubTypes
+        // See the comment in `get_string_kind_id` for why this can't delegate
+        // to `StringAttribute::get_string_value` directly.
+        self.try_into_string().expect("Attribute is not a string attribute");
 
         let mut length = 0;
         let cstr_ptr = unsafe { LLVMGetStringAttributeValue(self.attribute, &mut length) };
@@ -256,12 +738,419 @@ impl Attribute {
     /// ```
     #[llvm_versions(12.0..=latest)]
     pub fn get_type_value(&self) -> AnyTypeEnum {
-        assert!(self.is_type()); // FIXME: SubTypes
+        self.try_into_type()
+            .expect("Attribute is not a type attribute")
+            .get_type_value()
+    }
+
+    /// Gets the canonical LLVM name of this `Attribute`'s kind, regardless of
+    /// whether it's an enum or (on LLVM 12+) a type attribute.
+    ///
+    /// Returns `None` if this isn't an enum/type attribute, or if its kind id
+    /// doesn't map to a known [`AttributeKind`] (e.g. a target-specific
+    /// attribute).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::AttributeKind;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let kind_id = AttributeKind::NoInline.kind_id();
+    /// let enum_attribute = context.create_enum_attribute(kind_id, 0);
+    ///
+    /// assert_eq!(enum_attribute.kind_name(), Some("noinline"));
+    /// ```
+    #[llvm_versions(4.0..12.0)]
+    pub fn kind_name(self) -> Option<&'static str> {
+        let kind_id = self.try_into_enum()?.get_enum_kind_id();
+
+        AttributeKind::from_kind_id(kind_id).map(AttributeKind::name)
+    }
+
+    /// Gets the canonical LLVM name of this `Attribute`'s kind, regardless of
+    /// whether it's an enum or (on LLVM 12+) a type attribute.
+    ///
+    /// Returns `None` if this isn't an enum/type attribute, or if its kind id
+    /// doesn't map to a known [`AttributeKind`] (e.g. a target-specific
+    /// attribute).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::AttributeKind;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let kind_id = AttributeKind::NoInline.kind_id();
+    /// let enum_attribute = context.create_enum_attribute(kind_id, 0);
+    ///
+    /// assert_eq!(enum_attribute.kind_name(), Some("noinline"));
+    /// ```
+    #[llvm_versions(12.0..=latest)]
+    pub fn kind_name(self) -> Option<&'static str> {
+        let kind_id = self
+            .try_into_enum()
+            .map(|attribute| attribute.get_enum_kind_id())
+            .or_else(|| self.try_into_type().map(|attribute| attribute.get_enum_kind_id()))?;
+
+        AttributeKind::from_kind_id(kind_id).map(AttributeKind::name)
+    }
+}
+
+/// An `Attribute` that's known, at the type level, to be an enum attribute.
+///
+/// Obtained via [`Attribute::try_into_enum`]. Unlike [`Attribute::get_enum_value`]
+/// and friends, its accessors are infallible, since the subtype check already
+/// happened at downcast time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnumAttribute {
+    pub(crate) attribute: LLVMAttributeRef,
+}
+
+impl EnumAttribute {
+    /// Widens this `EnumAttribute` back into an untyped `Attribute`.
+    pub fn as_attribute(self) -> Attribute {
+        Attribute {
+            attribute: self.attribute,
+        }
+    }
+
+    /// Gets the kind id associated with this enum `Attribute`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let enum_attribute = context.create_enum_attribute(0, 10).try_into_enum().unwrap();
+    ///
+    /// assert_eq!(enum_attribute.get_enum_kind_id(), 0);
+    /// ```
+    pub fn get_enum_kind_id(self) -> u32 {
+        unsafe { LLVMGetEnumAttributeKind(self.attribute) }
+    }
 
+    /// Gets the `AttributeKind` associated with this enum `Attribute`, if
+    /// it's one of LLVM's recognized builtin attributes.
+    pub fn get_enum_kind(self) -> Option<AttributeKind> {
+        AttributeKind::from_kind_id(self.get_enum_kind_id())
+    }
+
+    /// Gets the value associated with this enum `Attribute`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let enum_attribute = context.create_enum_attribute(0, 10).try_into_enum().unwrap();
+    ///
+    /// assert_eq!(enum_attribute.get_enum_value(), 10);
+    /// ```
+    pub fn get_enum_value(self) -> u64 {
+        unsafe { LLVMGetEnumAttributeValue(self.attribute) }
+    }
+}
+
+/// An `Attribute` that's known, at the type level, to be a string attribute.
+///
+/// Obtained via [`Attribute::try_into_string`]. Unlike [`Attribute::get_string_value`]
+/// and friends, its accessors are infallible, since the subtype check already
+/// happened at downcast time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StringAttribute {
+    pub(crate) attribute: LLVMAttributeRef,
+}
+
+impl StringAttribute {
+    /// Widens this `StringAttribute` back into an untyped `Attribute`.
+    pub fn as_attribute(self) -> Attribute {
+        Attribute {
+            attribute: self.attribute,
+        }
+    }
+
+    /// Gets the string kind id associated with this string `Attribute`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let string_attribute = context.create_string_attribute("my_key", "my_val").try_into_string().unwrap();
+    ///
+    /// assert_eq!(string_attribute.get_string_kind_id().to_str(), Ok("my_key"));
+    /// ```
+    pub fn get_string_kind_id(&self) -> &CStr {
+        let mut length = 0;
+        let cstr_ptr = unsafe { LLVMGetStringAttributeKind(self.attribute, &mut length) };
+
+        unsafe { CStr::from_ptr(cstr_ptr) }
+    }
+
+    /// Gets the string value associated with this string `Attribute`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let string_attribute = context.create_string_attribute("my_key", "my_val").try_into_string().unwrap();
+    ///
+    /// assert_eq!(string_attribute.get_string_value().to_str(), Ok("my_val"));
+    /// ```
+    pub fn get_string_value(&self) -> &CStr {
+        let mut length = 0;
+        let cstr_ptr = unsafe { LLVMGetStringAttributeValue(self.attribute, &mut length) };
+
+        unsafe { CStr::from_ptr(cstr_ptr) }
+    }
+}
+
+/// An `Attribute` that's known, at the type level, to be a type attribute.
+///
+/// Obtained via [`Attribute::try_into_type`]. Unlike [`Attribute::get_type_value`],
+/// its accessor is infallible, since the subtype check already happened at
+/// downcast time.
+#[llvm_versions(12.0..=latest)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TypeAttribute {
+    pub(crate) attribute: LLVMAttributeRef,
+}
+
+#[llvm_versions(12.0..=latest)]
+impl TypeAttribute {
+    /// Widens this `TypeAttribute` back into an untyped `Attribute`.
+    pub fn as_attribute(self) -> Attribute {
+        Attribute {
+            attribute: self.attribute,
+        }
+    }
+
+    /// Gets the kind id associated with this type `Attribute`.
+    pub fn get_enum_kind_id(self) -> u32 {
+        unsafe { LLVMGetEnumAttributeKind(self.attribute) }
+    }
+
+    /// Gets the type associated with this type `Attribute`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::attributes::Attribute;
+    /// use inkwell::types::AnyType;
+    ///
+    /// let context = Context::create();
+    /// let kind_id = Attribute::get_named_enum_kind_id("sret");
+    /// let any_type = context.i32_type().as_any_type_enum();
+    /// let type_attribute = context.create_type_attribute(
+    ///     kind_id,
+    ///     any_type,
+    /// ).try_into_type().unwrap();
+    ///
+    /// assert_eq!(type_attribute.get_type_value(), any_type);
+    /// assert_ne!(type_attribute.get_type_value(), context.i64_type().as_any_type_enum());
+    /// ```
+    pub fn get_type_value(self) -> AnyTypeEnum {
         unsafe { AnyTypeEnum::new(LLVMGetTypeAttributeValue(self.attribute)) }
     }
 }
 
+/// One of the three memory locations distinguished by the unified `memory(...)`
+/// effects attribute that LLVM 15+ uses in place of `readonly`, `readnone`,
+/// `writeonly`, `argmemonly`, and `inaccessiblememonly`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Location {
+    /// Memory reachable through a function's pointer arguments.
+    ArgMem,
+    /// Memory only reachable through "inaccessible" memory, i.e. memory that
+    /// isn't reachable through any argument or global.
+    InaccessibleMem,
+    /// Any other memory, i.e. everything not covered by `ArgMem` or
+    /// `InaccessibleMem` (this includes global memory).
+    Other,
+}
+
+impl Location {
+    fn bit_offset(self) -> u32 {
+        match self {
+            Location::ArgMem => 0,
+            Location::InaccessibleMem => 1,
+            Location::Other => 2,
+        }
+    }
+}
+
+/// Whether a [`Location`] may be read from, written to, both, or neither, as
+/// packed into the `memory(...)` effects attribute's 2-bit-per-location value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ModRef {
+    /// Neither reads nor writes this location.
+    NoModRef = 0,
+    /// May read this location.
+    Ref = 1,
+    /// May write this location.
+    Mod = 2,
+    /// May read or write this location.
+    ModRef = 3,
+}
+
+impl ModRef {
+    fn from_bits(bits: u64) -> Self {
+        match bits & 0b11 {
+            0 => ModRef::NoModRef,
+            1 => ModRef::Ref,
+            2 => ModRef::Mod,
+            3 => ModRef::ModRef,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// A builder for the encoded `u64` value of the unified `memory(...)` effects
+/// enum attribute (LLVM 15+).
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::attributes::{Location, MemoryEffects, ModRef};
+///
+/// let effects = MemoryEffects::builder()
+///     .set(Location::ArgMem, ModRef::Ref)
+///     .build();
+///
+/// assert_eq!(effects.get(Location::ArgMem), ModRef::Ref);
+/// assert_eq!(effects.get(Location::Other), ModRef::NoModRef);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct MemoryEffectsBuilder {
+    value: u64,
+}
+
+impl MemoryEffectsBuilder {
+    /// Sets the `ModRef` for a single `Location`, leaving the others untouched.
+    pub fn set(mut self, location: Location, mod_ref: ModRef) -> Self {
+        let mask = 0b11 << (location.bit_offset() * 2);
+
+        self.value = (self.value & !mask) | ((mod_ref as u64) << (location.bit_offset() * 2));
+        self
+    }
+
+    /// Finishes building, producing the encoded `MemoryEffects`.
+    pub fn build(self) -> MemoryEffects {
+        MemoryEffects { value: self.value }
+    }
+}
+
+/// The encoded value of the unified `memory(...)` effects enum attribute
+/// (LLVM 15+), which replaced the separate `readonly`, `readnone`,
+/// `writeonly`, `argmemonly`, and `inaccessiblememonly` attributes.
+///
+/// Build one with [`MemoryEffects::builder`] or one of the presets below, and
+/// turn it into an `Attribute` with [`MemoryEffects::as_enum_attribute`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct MemoryEffects {
+    value: u64,
+}
+
+impl MemoryEffects {
+    /// Starts building a `MemoryEffects` value one `Location` at a time.
+    pub fn builder() -> MemoryEffectsBuilder {
+        MemoryEffectsBuilder::default()
+    }
+
+    /// No access to any memory at all.
+    pub fn none() -> Self {
+        Self::builder().build()
+    }
+
+    /// Read access to any memory location, but no writes (replaces `readonly`).
+    pub fn read_only() -> Self {
+        Self::builder()
+            .set(Location::ArgMem, ModRef::Ref)
+            .set(Location::InaccessibleMem, ModRef::Ref)
+            .set(Location::Other, ModRef::Ref)
+            .build()
+    }
+
+    /// Write access to any memory location, but no reads (replaces `writeonly`).
+    pub fn write_only() -> Self {
+        Self::builder()
+            .set(Location::ArgMem, ModRef::Mod)
+            .set(Location::InaccessibleMem, ModRef::Mod)
+            .set(Location::Other, ModRef::Mod)
+            .build()
+    }
+
+    /// Access restricted to pointer-argument memory, with the given
+    /// read/write permissions (replaces `argmemonly`).
+    pub fn arg_mem_only(mod_ref: ModRef) -> Self {
+        Self::builder().set(Location::ArgMem, mod_ref).build()
+    }
+
+    /// Access restricted to inaccessible memory, with the given read/write
+    /// permissions (replaces `inaccessiblememonly`).
+    pub fn inaccessible_mem_only(mod_ref: ModRef) -> Self {
+        Self::builder().set(Location::InaccessibleMem, mod_ref).build()
+    }
+
+    /// Gets the `ModRef` permissions for a single `Location`.
+    pub fn get(self, location: Location) -> ModRef {
+        ModRef::from_bits(self.value >> (location.bit_offset() * 2))
+    }
+
+    /// Gets the packed `u64` value to pass as an enum `Attribute`'s value,
+    /// alongside the `memory` kind id.
+    pub fn to_enum_value(self) -> u64 {
+        self.value
+    }
+
+    /// Decodes a `MemoryEffects` back out of the raw `u64` value of a
+    /// `memory(...)` enum `Attribute`, e.g. from [`EnumAttribute::get_enum_value`].
+    pub fn from_enum_value(value: u64) -> Self {
+        MemoryEffects { value }
+    }
+
+    /// Builds the `memory(...)` enum `Attribute` for this `MemoryEffects`,
+    /// given the `memory` attribute's kind id (looked up via
+    /// `AttributeKind::Memory.kind_id()` on LLVM versions that define it).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::{AttributeKind, MemoryEffects};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let memory_kind_id = AttributeKind::Memory.kind_id();
+    /// let attribute = MemoryEffects::read_only().as_enum_attribute(&context, memory_kind_id);
+    /// ```
+    pub fn as_enum_attribute(self, context: &Context, memory_kind_id: u32) -> EnumAttribute {
+        context
+            .create_enum_attribute(memory_kind_id, self.to_enum_value())
+            .try_into_enum()
+            .expect("create_enum_attribute always returns an enum attribute")
+    }
+}
+
+impl EnumAttribute {
+    /// Decodes this enum `Attribute`'s value as a `memory(...)` effects value.
+    ///
+    /// This doesn't check that the attribute's kind id is actually `memory`;
+    /// callers should confirm that via [`EnumAttribute::get_enum_kind`] (or
+    /// the raw kind id) first.
+    pub fn get_memory_effects(self) -> MemoryEffects {
+        MemoryEffects::from_enum_value(self.get_enum_value())
+    }
+}
+
 /// An `AttributeLoc` determines where on a function an attribute is assigned to.
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum AttributeLoc {
@@ -288,6 +1177,30 @@ impl AttributeLoc {
             AttributeLoc::Function => u32::max_value(),
         }
     }
+
+    /// Counts how many `Attribute`s are present at this location on `value`,
+    /// which must be a `FunctionValue`'s underlying `LLVMValueRef` (call
+    /// sites go through `LLVMGetCallSiteAttributeCount` instead).
+    pub(crate) unsafe fn count_attributes(self, value: LLVMValueRef) -> u32 {
+        LLVMGetAttributeCountAtIndex(value, self.get_index())
+    }
+
+    /// Collects every `Attribute` present at this location on `value`, which
+    /// must be a `FunctionValue`'s underlying `LLVMValueRef` (call sites go
+    /// through `LLVMGetCallSiteAttributes` instead).
+    ///
+    /// This makes it possible to inspect, diff, or copy the full attribute
+    /// set at a location without already knowing every kind id to probe for.
+    pub(crate) unsafe fn get_attributes(self, value: LLVMValueRef) -> Vec<Attribute> {
+        let count = self.count_attributes(value) as usize;
+        let mut attribute_refs = Vec::with_capacity(count);
+
+        LLVMGetAttributesAtIndex(value, self.get_index(), attribute_refs.as_mut_ptr());
+
+        attribute_refs.set_len(count);
+
+        attribute_refs.into_iter().map(|a| Attribute::new(a)).collect()
+    }
 }
 
 #[cfg(feature = "internal-getters")]
@@ -0,0 +1,47 @@
+use llvm_sys::core::{LLVMGetCallSiteAttributeCount, LLVMGetCallSiteAttributes};
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::marker::PhantomData;
+
+use crate::attributes::{Attribute, AttributeLoc};
+
+/// A `CallSiteValue` is a reference to a call or invoke instruction, i.e. a
+/// specific call site rather than the `FunctionValue` being called.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CallSiteValue<'ctx> {
+    pub(crate) call_site_value: LLVMValueRef,
+    _marker: PhantomData<&'ctx ()>,
+}
+
+impl<'ctx> CallSiteValue<'ctx> {
+    pub(crate) unsafe fn new(call_site_value: LLVMValueRef) -> Self {
+        debug_assert!(!call_site_value.is_null());
+
+        CallSiteValue {
+            call_site_value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Counts how many `Attribute`s are present at `loc` on this call site.
+    pub fn count_attributes(self, loc: AttributeLoc) -> u32 {
+        unsafe { LLVMGetCallSiteAttributeCount(self.call_site_value, loc.get_index()) }
+    }
+
+    /// Collects every `Attribute` present at `loc` on this call site.
+    ///
+    /// This makes it possible to inspect, diff, or copy the full attribute
+    /// set at a location without already knowing every kind id to probe for.
+    pub fn attributes(self, loc: AttributeLoc) -> Vec<Attribute> {
+        let count = self.count_attributes(loc) as usize;
+        let mut attribute_refs = Vec::with_capacity(count);
+
+        unsafe {
+            LLVMGetCallSiteAttributes(self.call_site_value, loc.get_index(), attribute_refs.as_mut_ptr());
+
+            attribute_refs.set_len(count);
+
+            attribute_refs.into_iter().map(|a| Attribute::new(a)).collect()
+        }
+    }
+}
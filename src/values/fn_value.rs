@@ -0,0 +1,55 @@
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::marker::PhantomData;
+
+use crate::attributes::{Attribute, AttributeLoc};
+
+/// A `FunctionValue` is a reference to an LLVM function definition or declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FunctionValue<'ctx> {
+    pub(crate) fn_value: LLVMValueRef,
+    _marker: PhantomData<&'ctx ()>,
+}
+
+impl<'ctx> FunctionValue<'ctx> {
+    pub(crate) unsafe fn new(fn_value: LLVMValueRef) -> Self {
+        debug_assert!(!fn_value.is_null());
+
+        FunctionValue {
+            fn_value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Counts how many `Attribute`s are present at `loc` on this `FunctionValue`.
+    pub fn count_attributes(self, loc: AttributeLoc) -> u32 {
+        unsafe { loc.count_attributes(self.fn_value) }
+    }
+
+    /// Collects every `Attribute` present at `loc` on this `FunctionValue`.
+    ///
+    /// This makes it possible to inspect, diff, or copy the full attribute
+    /// set at a location, e.g. when cloning or wrapping a function, without
+    /// already knowing every kind id to probe for.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::attributes::{Attribute, AttributeLoc};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let kind_id = Attribute::get_named_enum_kind_id("alwaysinline");
+    ///
+    /// function.add_attribute(AttributeLoc::Function, context.create_enum_attribute(kind_id, 0));
+    ///
+    /// assert_eq!(function.attributes(AttributeLoc::Function).len(), 1);
+    /// ```
+    pub fn attributes(self, loc: AttributeLoc) -> Vec<Attribute> {
+        unsafe { loc.get_attributes(self.fn_value) }
+    }
+}
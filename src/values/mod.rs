@@ -0,0 +1,5 @@
+mod callable_value;
+mod fn_value;
+
+pub use callable_value::CallSiteValue;
+pub use fn_value::FunctionValue;